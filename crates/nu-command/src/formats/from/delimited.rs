@@ -1,86 +1,520 @@
-use std::sync::{atomic::AtomicBool, Arc};
+use std::collections::HashMap;
+use std::sync::{atomic::AtomicBool, Arc, Mutex};
 
-use csv::{ReaderBuilder, Trim};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use csv::{ErrorKind, Position, ReaderBuilder, Trim};
 use nu_protocol::{IntoInterruptiblePipelineData, PipelineData, Record, ShellError, Span, Value};
 
+pub(super) mod index;
+pub(super) mod mmap;
+
+/// A target type a column can be coerced to via a `from csv`/`from tsv` schema.
+#[derive(Clone, Debug)]
+pub(super) enum ColumnType {
+    Int,
+    Float,
+    String,
+    Bool,
+    Binary,
+    DateTime { format: Option<String> },
+}
+
+fn column_type_from_str(name: &str, span: Span) -> Result<ColumnType, ShellError> {
+    match name {
+        "int" => Ok(ColumnType::Int),
+        "float" => Ok(ColumnType::Float),
+        "string" => Ok(ColumnType::String),
+        "bool" => Ok(ColumnType::Bool),
+        "binary" => Ok(ColumnType::Binary),
+        "datetime" => Ok(ColumnType::DateTime { format: None }),
+        _ => Err(ShellError::TypeMismatch {
+            err_message: format!(
+                "unknown schema type '{name}', expected one of: int, float, string, bool, binary, datetime"
+            ),
+            span,
+        }),
+    }
+}
+
+fn column_type_from_value(value: &Value) -> Result<ColumnType, ShellError> {
+    let span = value.span();
+    match value {
+        Value::String { val, .. } => column_type_from_str(val, span),
+        Value::Record { val, .. } => {
+            let ty = val
+                .get("type")
+                .ok_or_else(|| ShellError::MissingParameter {
+                    param_name: "type".into(),
+                    span,
+                })?
+                .as_str()?;
+            if ty != "datetime" {
+                return Err(ShellError::TypeMismatch {
+                    err_message: "only 'datetime' schema entries take a 'format'".into(),
+                    span,
+                });
+            }
+            let format = val
+                .get("format")
+                .map(|f| f.as_str().map(String::from))
+                .transpose()?;
+            Ok(ColumnType::DateTime { format })
+        }
+        _ => Err(ShellError::TypeMismatch {
+            err_message: "schema entries must be a type name or a {type: ..., format: ...} record"
+                .into(),
+            span,
+        }),
+    }
+}
+
+/// A resolved `--types` schema: column types keyed by header name or by
+/// positional index, with name taking precedence when both are given.
+#[derive(Clone)]
+pub(super) struct ColumnSchema {
+    by_name: HashMap<String, ColumnType>,
+    by_index: HashMap<usize, ColumnType>,
+}
+
+impl ColumnSchema {
+    fn type_for(&self, index: usize, name: &str) -> Option<&ColumnType> {
+        self.by_name.get(name).or_else(|| self.by_index.get(&index))
+    }
+}
+
+/// Parses the value passed to `from csv --types`/`from tsv --types` into a [`ColumnSchema`].
+pub fn schema_from_value(schema: Option<Value>) -> Result<Option<ColumnSchema>, ShellError> {
+    let Some(value) = schema else {
+        return Ok(None);
+    };
+    let span = value.span();
+    let Value::Record { val, .. } = value else {
+        return Err(ShellError::TypeMismatch {
+            err_message: "the schema must be a record mapping column names or indices to types"
+                .into(),
+            span,
+        });
+    };
+
+    let mut by_name = HashMap::new();
+    let mut by_index = HashMap::new();
+    for (col, ty_value) in val.into_iter() {
+        let ty = column_type_from_value(&ty_value)?;
+        match col.parse::<usize>() {
+            Ok(index) => {
+                by_index.insert(index, ty);
+            }
+            Err(_) => {
+                by_name.insert(col, ty);
+            }
+        }
+    }
+
+    Ok(Some(ColumnSchema { by_name, by_index }))
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "true" | "1" => Some(true),
+        "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parses the value passed to `from csv --null-values` into a list of tokens
+/// (e.g. `["", "NA", "NULL"]`) that should become `Value::nothing` on read.
+pub fn null_values_from_value(value: Option<Value>) -> Result<Vec<String>, ShellError> {
+    let Some(value) = value else {
+        return Ok(Vec::new());
+    };
+    let span = value.span();
+    let Value::List { vals, .. } = value else {
+        return Err(ShellError::TypeMismatch {
+            err_message: "null-values must be a list of strings".into(),
+            span,
+        });
+    };
+
+    vals.into_iter()
+        .map(|v| v.as_str().map(String::from))
+        .collect()
+}
+
+fn is_null_value(value: &str, null_values: &[String], case_sensitive: bool) -> bool {
+    null_values.iter().any(|token| {
+        if case_sensitive {
+            token == value
+        } else {
+            token.eq_ignore_ascii_case(value)
+        }
+    })
+}
+
+/// Parses `value` against a user-supplied `--types` datetime format.
+///
+/// `DateTime::parse_from_str` only succeeds when `fmt` includes an offset
+/// directive (`%z`/`%:z`); most user-supplied formats (e.g. `"%Y-%m-%d"`)
+/// don't have one, so fall back to `NaiveDateTime`/`NaiveDate` and assume UTC
+/// when no offset is present in the format.
+fn parse_datetime_with_format(value: &str, fmt: &str, span: Span) -> Option<Value> {
+    if let Ok(dt) = DateTime::parse_from_str(value, fmt) {
+        return Some(Value::date(dt, span));
+    }
+    if let Ok(dt) = NaiveDateTime::parse_from_str(value, fmt) {
+        return Some(Value::date(dt.and_utc().fixed_offset(), span));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, fmt) {
+        return Some(Value::date(
+            date.and_hms_opt(0, 0, 0)?.and_utc().fixed_offset(),
+            span,
+        ));
+    }
+    None
+}
+
+fn parse_typed_field(
+    value: &str,
+    ty: &ColumnType,
+    column: &str,
+    span: Span,
+) -> Result<Value, ShellError> {
+    let mismatch = |expected: &str| ShellError::GenericError(
+        "CSV type error".into(),
+        format!("column '{column}': could not parse '{value}' as {expected}"),
+        Some(span),
+        None,
+        vec![],
+    );
+
+    match ty {
+        ColumnType::String => Ok(Value::string(value.to_string(), span)),
+        ColumnType::Int => value
+            .parse::<i64>()
+            .map(|i| Value::int(i, span))
+            .map_err(|_| mismatch("int")),
+        ColumnType::Float => value
+            .parse::<f64>()
+            .map(|f| Value::float(f, span))
+            .map_err(|_| mismatch("float")),
+        ColumnType::Bool => parse_bool(value)
+            .map(|b| Value::bool(b, span))
+            .ok_or_else(|| mismatch("bool")),
+        ColumnType::Binary => Ok(Value::binary(value.as_bytes().to_vec(), span)),
+        ColumnType::DateTime { format } => match format {
+            Some(fmt) => {
+                parse_datetime_with_format(value, fmt, span).ok_or_else(|| mismatch("datetime"))
+            }
+            None => DateTime::parse_from_rfc3339(value)
+                .map(|dt| Value::date(dt, span))
+                .map_err(|_| mismatch("datetime")),
+        },
+    }
+}
+
+/// What to do when a record fails to parse.
+///
+/// `Abort` matches historical behavior: the pipeline stops at the first bad
+/// record. `SkipAndCollect` drops the offending record but keeps reading, so
+/// a mostly-valid file still yields its clean rows; the collected errors are
+/// reported together once the input is exhausted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum CsvErrorMode {
+    Abort,
+    SkipAndCollect,
+}
+
+pub fn error_mode_from_str(mode: Option<Value>) -> Result<CsvErrorMode, ShellError> {
+    match mode {
+        Some(v) => {
+            let span = v.span();
+            match v {
+                Value::String { val: item, .. } => match item.as_str() {
+                    "abort" => Ok(CsvErrorMode::Abort),
+                    "skip" => Ok(CsvErrorMode::SkipAndCollect),
+                    _ => Err(ShellError::TypeMismatch {
+                        err_message: "the only possible values for on_error are 'abort' and 'skip'"
+                            .into(),
+                        span,
+                    }),
+                },
+                _ => Ok(CsvErrorMode::Abort),
+            }
+        }
+        None => Ok(CsvErrorMode::Abort),
+    }
+}
+
+/// Finds the span within `base` that a `csv::Error`'s record points at: the
+/// first byte of the offending record when that offset lies inside `base`,
+/// and the whole of `base` otherwise.
+fn error_span(err: &csv::Error, base: Span) -> Span {
+    err.position()
+        .and_then(|pos: &Position| {
+            let start = base.start + pos.byte() as usize;
+            (start < base.end).then(|| Span::new(start, start + 1))
+        })
+        .unwrap_or(base)
+}
+
+/// Like [`error_span`], but for errors discovered after a record was
+/// successfully read by `csv` (e.g. a schema type mismatch).
+fn error_span_for_record(row: &csv::StringRecord, base: Span) -> Span {
+    row.position()
+        .and_then(|pos: &Position| {
+            let start = base.start + pos.byte() as usize;
+            (start < base.end).then(|| Span::new(start, start + 1))
+        })
+        .unwrap_or(base)
+}
+
+fn error_message(err: &csv::Error) -> String {
+    match err.kind() {
+        ErrorKind::UnequalLengths {
+            pos,
+            expected_len,
+            len,
+        } => format!(
+            "record {} has {len} fields, but the header has {expected_len} fields",
+            pos.as_ref().map(|p| p.record()).unwrap_or_default(),
+        ),
+        _ => err.to_string(),
+    }
+}
+
+struct ScanState {
+    headers: Vec<String>,
+    halted: bool,
+}
+
+/// Builds a single output row, coercing fields to their schema type when one
+/// is declared for that column, falling back to plain inference otherwise.
+fn build_row(
+    row: &csv::StringRecord,
+    headers: &[String],
+    schema: Option<&ColumnSchema>,
+    no_infer: bool,
+    null_values: &[String],
+    null_values_case_sensitive: bool,
+    span: Span,
+) -> Result<Value, ShellError> {
+    let mut output_row = Vec::with_capacity(row.len());
+
+    for (i, value) in row.iter().enumerate() {
+        let header = headers.get(i).map(String::as_str).unwrap_or_default();
+
+        if let Some(ty) = schema.and_then(|s| s.type_for(i, header)) {
+            output_row.push(parse_typed_field(value, ty, header, span)?);
+            continue;
+        }
+
+        if is_null_value(value, null_values, null_values_case_sensitive) {
+            output_row.push(Value::nothing(span));
+            continue;
+        }
+
+        if no_infer {
+            output_row.push(Value::string(value.to_string(), span));
+            continue;
+        }
+
+        if let Ok(i) = value.parse::<i64>() {
+            output_row.push(Value::int(i, span));
+        } else if let Ok(f) = value.parse::<f64>() {
+            output_row.push(Value::float(f, span));
+        } else if let Some(b) = parse_bool(value) {
+            output_row.push(Value::bool(b, span));
+        } else {
+            output_row.push(Value::string(value.to_string(), span));
+        }
+    }
+
+    Ok(Value::record(
+        Record {
+            cols: headers.to_vec(),
+            vals: output_row,
+        },
+        span,
+    ))
+}
+
+/// Checked `char` -> `u8` conversion. Also used by the writer side
+/// ([`crate::formats::to::delimited`]), so an out-of-range separator, quote,
+/// comment, or escape char is rejected consistently on both the read and
+/// write paths instead of silently truncating on read.
+pub(crate) fn char_to_u8(c: char, label: &str, span: Span) -> Result<u8, ShellError> {
+    u8::try_from(c).map_err(|err| ShellError::IncorrectValue {
+        msg: format!("Invalid {label}: {err}"),
+        val_span: span,
+        call_span: span,
+    })
+}
+
+pub(super) fn reader_builder(
+    config: &DelimitedReaderConfig,
+    span: Span,
+) -> Result<ReaderBuilder, ShellError> {
+    let mut builder = ReaderBuilder::new();
+    builder
+        .has_headers(!config.noheaders)
+        .flexible(config.flexible)
+        .delimiter(char_to_u8(config.separator, "separator", span)?)
+        .comment(
+            config
+                .comment
+                .map(|c| char_to_u8(c, "comment", span))
+                .transpose()?,
+        )
+        .quote(char_to_u8(config.quote, "quote", span)?)
+        .escape(
+            config
+                .escape
+                .map(|c| char_to_u8(c, "escape", span))
+                .transpose()?,
+        )
+        .trim(config.trim);
+    Ok(builder)
+}
+
 fn from_delimited_to_values<R>(
-    DelimitedReaderConfig {
-        separator,
-        record_separator: _,
-        comment,
-        quote,
-        escape,
-        noheaders,
-        flexible,
-        no_infer,
-        trim,
-    }: DelimitedReaderConfig,
+    config: DelimitedReaderConfig,
     reader: R,
     span: Span,
-) -> csv::Result<impl Iterator<Item = Value> + Send + 'static>
+) -> Result<impl Iterator<Item = Value> + Send + 'static, ShellError>
 where
     R: std::io::Read + Send + 'static,
 {
-    let mut reader = ReaderBuilder::new()
-        .has_headers(!noheaders)
-        .flexible(flexible)
-        .delimiter(separator as u8)
-        .comment(comment.map(|c| c as u8))
-        .quote(quote as u8)
-        .escape(escape.map(|c| c as u8))
-        .trim(trim)
-        .from_reader(reader);
-
-    let headers = if noheaders {
-        (1..=reader.headers()?.len())
+    let csv_err = |err: csv::Error| {
+        ShellError::GenericError("CSVError".into(), err.to_string(), Some(span), None, vec![])
+    };
+
+    let mut reader = reader_builder(&config, span)?.from_reader(reader);
+
+    let headers = if config.noheaders {
+        (1..=reader.headers().map_err(csv_err)?.len())
             .map(|i| format!("column{i}"))
             .collect::<Vec<String>>()
     } else {
-        reader.headers()?.iter().map(String::from).collect()
+        reader
+            .headers()
+            .map_err(csv_err)?
+            .iter()
+            .map(String::from)
+            .collect()
     };
 
-    Ok(reader
-        .into_records()
-        .scan(
+    Ok(from_delimited_record_stream(config, reader, headers, span))
+}
+
+/// Turns an already-positioned `csv::Reader` plus resolved `headers` into the
+/// same row/error stream `from_delimited_to_values` produces. Shared with the
+/// [`index`] module, which seeks a reader straight to a record's byte offset
+/// instead of reading from the start of the file.
+pub(super) fn from_delimited_record_stream<'r, R>(
+    DelimitedReaderConfig {
+        no_infer,
+        on_error,
+        schema,
+        null_values,
+        null_values_case_sensitive,
+        ..
+    }: DelimitedReaderConfig,
+    reader: csv::Reader<R>,
+    headers: Vec<String>,
+    span: Span,
+) -> impl Iterator<Item = Value> + Send + 'r
+where
+    R: std::io::Read + Send + 'r,
+{
+    let collected_errors: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+    let summary_errors = Arc::clone(&collected_errors);
+
+    let records = reader.into_records().scan(
+        ScanState {
             headers,
-            move |headers, row: csv::Result<csv::StringRecord>| {
-                let row = match row {
-                    Err(err) => {
-                        eprintln!("Error parsing CSV record: {}", err);
-                        return None;
-                    }
-                    Ok(row) => row,
-                };
+            halted: false,
+        },
+        move |state, row: csv::Result<csv::StringRecord>| {
+            if state.halted {
+                return None;
+            }
 
-                let mut output_row = Vec::with_capacity(row.len());
+            let outcome = match row {
+                Err(err) => Err((error_message(&err), error_span(&err, span))),
+                Ok(row) => build_row(
+                    &row,
+                    &state.headers,
+                    schema.as_ref(),
+                    no_infer,
+                    &null_values,
+                    null_values_case_sensitive,
+                    span,
+                )
+                .map_err(|err| (err.to_string(), error_span_for_record(&row, span))),
+            };
 
-                for value in row.iter() {
-                    if no_infer {
-                        output_row.push(Value::string(value.to_string(), span));
-                        continue;
-                    }
+            match outcome {
+                Ok(value) => Some(value),
+                Err((message, err_span)) => {
+                    let value = Value::error(
+                        ShellError::GenericError(
+                            "CSV parse error".into(),
+                            message.clone(),
+                            Some(err_span),
+                            None,
+                            vec![],
+                        ),
+                        span,
+                    );
 
-                    if let Ok(i) = value.parse::<i64>() {
-                        output_row.push(Value::int(i, span));
-                    } else if let Ok(f) = value.parse::<f64>() {
-                        output_row.push(Value::float(f, span));
-                    } else {
-                        output_row.push(Value::string(value.to_string(), span));
+                    match on_error {
+                        CsvErrorMode::Abort => {
+                            state.halted = true;
+                            Some(value)
+                        }
+                        CsvErrorMode::SkipAndCollect => {
+                            collected_errors
+                                .lock()
+                                .expect("collected_errors mutex poisoned")
+                                .push(message);
+                            Some(Value::nothing(span))
+                        }
                     }
                 }
+            }
+        },
+    );
 
-                Some(Value::record(
-                    Record {
-                        cols: headers.clone(),
-                        vals: output_row,
-                    },
+    // Drop the placeholder `nothing`s emitted for skipped rows, then surface
+    // any collected errors as a single final error value once the input is
+    // exhausted.
+    let records = records.filter(|value| !matches!(value, Value::Nothing { .. }));
+    records
+        .chain(std::iter::from_fn(move || {
+            let mut errors = summary_errors
+                .lock()
+                .expect("collected_errors mutex poisoned");
+            if errors.is_empty() {
+                None
+            } else {
+                let count = errors.len();
+                let msg = errors.join("\n");
+                errors.clear();
+                Some(Value::error(
+                    ShellError::GenericError(
+                        "CSV parse errors".into(),
+                        format!("{count} row(s) could not be parsed and were skipped:\n{msg}"),
+                        Some(span),
+                        None,
+                        vec![],
+                    ),
                     span,
                 ))
-            },
-        )
-        .fuse())
+            }
+        }))
+        .fuse()
 }
 
+#[derive(Clone)]
 pub(super) struct DelimitedReaderConfig {
     pub separator: char,
     pub record_separator: char,
@@ -91,6 +525,33 @@ pub(super) struct DelimitedReaderConfig {
     pub flexible: bool,
     pub no_infer: bool,
     pub trim: Trim,
+    pub on_error: CsvErrorMode,
+    pub schema: Option<ColumnSchema>,
+    pub null_values: Vec<String>,
+    pub null_values_case_sensitive: bool,
+    pub mmap_workers: usize,
+}
+
+#[cfg(test)]
+impl Default for DelimitedReaderConfig {
+    fn default() -> Self {
+        DelimitedReaderConfig {
+            separator: ',',
+            record_separator: '\n',
+            comment: None,
+            quote: '"',
+            escape: None,
+            noheaders: false,
+            flexible: false,
+            no_infer: false,
+            trim: Trim::None,
+            on_error: CsvErrorMode::Abort,
+            schema: None,
+            null_values: Vec::new(),
+            null_values_case_sensitive: false,
+            mmap_workers: 1,
+        }
+    }
 }
 
 pub(super) fn from_delimited_data(
@@ -99,6 +560,14 @@ pub(super) fn from_delimited_data(
     span: Span,
     ctrlc: Option<Arc<AtomicBool>>,
 ) -> Result<PipelineData, ShellError> {
+    if let Some((path, metadata)) = mmap::file_backed_source(&input) {
+        if let Some(values) = mmap::read_mmapped(&config, &path, config.mmap_workers, span) {
+            return Ok(values?
+                .into_iter()
+                .into_pipeline_data_with_metadata(Some(metadata), ctrlc));
+        }
+    }
+
     let (reader, span, metadata) = input.into_reader(
         span,
         Some(
@@ -110,12 +579,7 @@ pub(super) fn from_delimited_data(
         ),
     )?;
 
-    let csv_err = |err: csv::Error| {
-        ShellError::GenericError("CSVError".into(), err.to_string(), Some(span), None, vec![])
-    };
-
-    Ok(from_delimited_to_values(config, reader, span)
-        .map_err(csv_err)?
+    Ok(from_delimited_to_values(config, reader, span)?
         .into_pipeline_data_with_metadata(metadata, ctrlc))
 }
 
@@ -143,3 +607,113 @@ pub fn trim_from_str(trim: Option<Value>) -> Result<Trim, ShellError> {
         _ => Ok(Trim::None),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn base_config(on_error: CsvErrorMode) -> DelimitedReaderConfig {
+        DelimitedReaderConfig {
+            on_error,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn char_to_u8_rejects_a_multibyte_separator() {
+        let span = Span::test_data();
+        assert!(char_to_u8('é', "separator", span).is_err());
+        assert!(char_to_u8(',', "separator", span).is_ok());
+    }
+
+    #[test]
+    fn skip_and_collect_keeps_good_rows_and_reports_bad_ones() {
+        let span = Span::test_data();
+        let csv = "a,b\n1,2\n3\n4,5\n";
+        let config = base_config(CsvErrorMode::SkipAndCollect);
+        let values: Vec<Value> =
+            from_delimited_to_values(config, Cursor::new(csv.as_bytes().to_vec()), span)
+                .expect("builds reader")
+                .collect();
+
+        // both good rows survive, plus one trailing aggregated error value
+        // for the malformed row in between - nothing is silently dropped.
+        assert_eq!(values.len(), 3);
+        assert!(matches!(values[2], Value::Error { .. }));
+    }
+
+    #[test]
+    fn abort_halts_the_iterator_at_the_first_bad_row() {
+        let span = Span::test_data();
+        let csv = "a,b\n1,2\n3\n4,5\n";
+        let config = base_config(CsvErrorMode::Abort);
+        let values: Vec<Value> =
+            from_delimited_to_values(config, Cursor::new(csv.as_bytes().to_vec()), span)
+                .expect("builds reader")
+                .collect();
+
+        assert_eq!(values.len(), 2);
+        assert!(matches!(values[1], Value::Error { .. }));
+    }
+
+    #[test]
+    fn parse_typed_field_reports_a_spanned_mismatch() {
+        let span = Span::test_data();
+        let err = parse_typed_field("not-a-number", &ColumnType::Int, "age", span)
+            .expect_err("non-numeric input should not coerce to int");
+        let ShellError::GenericError(_, msg, ..) = err else {
+            panic!("expected a GenericError")
+        };
+        assert!(msg.contains("age"));
+    }
+
+    #[test]
+    fn datetime_format_without_an_offset_directive_assumes_utc() {
+        let span = Span::test_data();
+        let ty = ColumnType::DateTime {
+            format: Some("%Y-%m-%d %H:%M:%S".into()),
+        };
+        let value = parse_typed_field("2024-01-02 03:04:05", &ty, "ts", span)
+            .expect("falls back to NaiveDateTime + UTC when the format has no offset");
+        assert!(matches!(value, Value::Date { .. }));
+    }
+
+    #[test]
+    fn datetime_format_without_a_time_component_assumes_midnight_utc() {
+        let span = Span::test_data();
+        let ty = ColumnType::DateTime {
+            format: Some("%Y-%m-%d".into()),
+        };
+        let value = parse_typed_field("2024-01-02", &ty, "ts", span)
+            .expect("falls back to NaiveDate + midnight UTC");
+        assert!(matches!(value, Value::Date { .. }));
+    }
+
+    #[test]
+    fn null_token_matching_is_case_insensitive_by_default() {
+        let tokens = vec!["NA".to_string()];
+        assert!(is_null_value("na", &tokens, false));
+        assert!(!is_null_value("na", &tokens, true));
+        assert!(is_null_value("NA", &tokens, true));
+    }
+
+    #[test]
+    fn build_row_turns_null_tokens_into_nothing_and_infers_bool() {
+        let span = Span::test_data();
+        let mut record = csv::StringRecord::new();
+        record.push_field("NA");
+        record.push_field("true");
+        let headers = vec!["a".to_string(), "b".to_string()];
+        let null_values = vec!["NA".to_string()];
+
+        let value = build_row(&record, &headers, None, false, &null_values, false, span)
+            .expect("builds row");
+        let Value::Record { val, .. } = value else {
+            panic!("expected a record")
+        };
+
+        assert!(matches!(val.get("a"), Some(Value::Nothing { .. })));
+        assert!(matches!(val.get("b"), Some(Value::Bool { val: true, .. })));
+    }
+}
@@ -0,0 +1,271 @@
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+use csv::Position;
+use nu_protocol::{ShellError, Span, Value};
+
+use super::{from_delimited_record_stream, reader_builder, CsvErrorMode, DelimitedReaderConfig};
+
+/// Width in bytes of each big-endian `u64` entry in a `.idx` file.
+const OFFSET_WIDTH: usize = std::mem::size_of::<u64>();
+
+fn io_err(err: std::io::Error, path: &Path, span: Span) -> ShellError {
+    ShellError::GenericError(
+        "CSV index I/O error".into(),
+        format!("{}: {err}", path.display()),
+        Some(span),
+        None,
+        vec![],
+    )
+}
+
+fn csv_err(err: csv::Error, span: Span) -> ShellError {
+    ShellError::GenericError("CSVError".into(), err.to_string(), Some(span), None, vec![])
+}
+
+/// Builds a sidecar index for `input_path`, so that later reads can seek
+/// straight to any record instead of scanning the whole file.
+///
+/// Takes a path rather than a stream and reads it once from the start with a
+/// plain [`File`], so streamed/piped input, which has no path, cannot be
+/// passed to it.
+pub fn build_index(
+    config: &DelimitedReaderConfig,
+    input_path: &Path,
+    index_path: &Path,
+    span: Span,
+) -> Result<(), ShellError> {
+    let file = File::open(input_path).map_err(|err| io_err(err, input_path, span))?;
+    let mut reader = reader_builder(config, span)?.from_reader(file);
+
+    // Resolving headers advances the reader past the header line, so its
+    // position afterward is the start of record 0 - store that separately,
+    // since it's the one offset the read loop below never produces itself.
+    // Without headers the consumed line is record 0 itself, starting at 0.
+    reader.headers().map_err(|err| csv_err(err, span))?;
+    let header_offset = if config.noheaders {
+        0
+    } else {
+        reader.position().byte()
+    };
+
+    let mut offsets = vec![header_offset];
+    let mut record = csv::ByteRecord::new();
+    loop {
+        if !reader
+            .read_byte_record(&mut record)
+            .map_err(|err| csv_err(err, span))?
+        {
+            break;
+        }
+        offsets.push(reader.position().byte());
+    }
+    // The last entry is the offset just past the final record (i.e. EOF),
+    // which isn't the start of any record - drop it before writing.
+    offsets.pop();
+
+    let mut writer =
+        BufWriter::new(File::create(index_path).map_err(|err| io_err(err, index_path, span))?);
+    for offset in &offsets {
+        writer
+            .write_all(&offset.to_be_bytes())
+            .map_err(|err| io_err(err, index_path, span))?;
+    }
+    writer
+        .write_all(&(offsets.len() as u64).to_be_bytes())
+        .map_err(|err| io_err(err, index_path, span))?;
+    writer
+        .flush()
+        .map_err(|err| io_err(err, index_path, span))
+}
+
+/// Reads back the per-record byte offsets written by [`build_index`].
+fn read_offsets(index_path: &Path, span: Span) -> Result<Vec<u64>, ShellError> {
+    let mut bytes = Vec::new();
+    File::open(index_path)
+        .map_err(|err| io_err(err, index_path, span))?
+        .read_to_end(&mut bytes)
+        .map_err(|err| io_err(err, index_path, span))?;
+
+    if bytes.len() < OFFSET_WIDTH || bytes.len() % OFFSET_WIDTH != 0 {
+        return Err(ShellError::GenericError(
+            "Invalid CSV index".into(),
+            format!("{} is not a valid index file", index_path.display()),
+            Some(span),
+            None,
+            vec![],
+        ));
+    }
+
+    let mut words = bytes
+        .chunks_exact(OFFSET_WIDTH)
+        .map(|chunk| u64::from_be_bytes(chunk.try_into().expect("chunk is OFFSET_WIDTH bytes")));
+    let count = words.next_back().unwrap_or(0) as usize;
+    let offsets: Vec<u64> = words.collect();
+
+    if offsets.len() != count {
+        return Err(ShellError::GenericError(
+            "Invalid CSV index".into(),
+            format!(
+                "{} declares {count} record(s) but contains {} offset(s)",
+                index_path.display(),
+                offsets.len()
+            ),
+            Some(span),
+            None,
+            vec![],
+        ));
+    }
+
+    Ok(offsets)
+}
+
+/// Streams `rows` records starting at record `skip`, seeking directly to its
+/// byte offset via the sidecar index instead of scanning every row before it.
+pub fn read_indexed(
+    config: DelimitedReaderConfig,
+    input_path: &Path,
+    index_path: &Path,
+    skip: usize,
+    rows: usize,
+    span: Span,
+) -> Result<impl Iterator<Item = Value> + Send + 'static, ShellError> {
+    // `from_delimited_record_stream`'s skip-and-collect summary error is only
+    // emitted once its underlying iterator is exhausted, but `.take(rows)`
+    // below stops pulling before that point whenever `rows` is smaller than
+    // the remaining record count - silently dropping any errors collected
+    // for skipped rows in that slice. Reject the mode here instead of
+    // letting it silently reintroduce chunk0-1's original failure mode.
+    if config.on_error == CsvErrorMode::SkipAndCollect {
+        return Err(ShellError::GenericError(
+            "Unsupported CSV index configuration".into(),
+            "on_error: 'skip' is not supported together with --index, since errors in skipped \
+             rows past the requested --rows slice would go unreported; use on_error: 'abort' \
+             when reading through an index"
+                .into(),
+            Some(span),
+            None,
+            vec![],
+        ));
+    }
+
+    let offsets = read_offsets(index_path, span)?;
+    let start = *offsets.get(skip).ok_or_else(|| {
+        ShellError::GenericError(
+            "CSV index out of range".into(),
+            format!(
+                "requested record {skip}, but the index only has {} record(s)",
+                offsets.len()
+            ),
+            Some(span),
+            None,
+            vec![],
+        )
+    })?;
+
+    let file = File::open(input_path).map_err(|err| io_err(err, input_path, span))?;
+    let mut reader = reader_builder(&config, span)?.from_reader(file);
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|err| csv_err(err, span))?
+        .iter()
+        .map(String::from)
+        .collect();
+
+    let mut seek_to = Position::new();
+    seek_to.set_byte(start);
+    reader.seek(seek_to).map_err(|err| csv_err(err, span))?;
+
+    Ok(from_delimited_record_stream(config, reader, headers, span).take(rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_config() -> DelimitedReaderConfig {
+        DelimitedReaderConfig::default()
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("nu_csv_index_test_{}_{name}", std::process::id()))
+    }
+
+    #[test]
+    fn build_and_read_indexed_round_trips_rows() {
+        let span = Span::test_data();
+        let csv_path = temp_path("data.csv");
+        let idx_path = temp_path("data.idx");
+        std::fs::write(&csv_path, "a,b\n1,2\n3,4\n5,6\n").expect("writes fixture");
+
+        build_index(&base_config(), &csv_path, &idx_path, span).expect("builds index");
+        let values: Vec<Value> = read_indexed(base_config(), &csv_path, &idx_path, 1, 1, span)
+            .expect("reads indexed")
+            .collect();
+
+        assert_eq!(values.len(), 1);
+
+        std::fs::remove_file(&csv_path).ok();
+        std::fs::remove_file(&idx_path).ok();
+    }
+
+    #[test]
+    fn read_indexed_rejects_out_of_range_skip() {
+        let span = Span::test_data();
+        let csv_path = temp_path("range.csv");
+        let idx_path = temp_path("range.idx");
+        std::fs::write(&csv_path, "a,b\n1,2\n").expect("writes fixture");
+        build_index(&base_config(), &csv_path, &idx_path, span).expect("builds index");
+
+        let err = match read_indexed(base_config(), &csv_path, &idx_path, 5, 1, span) {
+            Ok(_) => panic!("skip past the end of the index should error"),
+            Err(err) => err,
+        };
+        assert!(err.to_string().contains("out of range"));
+
+        std::fs::remove_file(&csv_path).ok();
+        std::fs::remove_file(&idx_path).ok();
+    }
+
+    #[test]
+    fn read_indexed_rejects_skip_and_collect_error_mode() {
+        let span = Span::test_data();
+        let mut config = base_config();
+        config.on_error = CsvErrorMode::SkipAndCollect;
+
+        let err = match read_indexed(
+            config,
+            std::path::Path::new("/nonexistent.csv"),
+            std::path::Path::new("/nonexistent.idx"),
+            0,
+            1,
+            span,
+        ) {
+            Ok(_) => panic!(
+                "skip-and-collect can silently drop errors past a .take(rows) truncation"
+            ),
+            Err(err) => err,
+        };
+        let ShellError::GenericError(_, msg, ..) = err else {
+            panic!("expected a GenericError")
+        };
+        assert!(msg.to_lowercase().contains("skip"));
+    }
+
+    #[test]
+    fn truncated_index_file_is_rejected() {
+        let span = Span::test_data();
+        let idx_path = temp_path("truncated.idx");
+        std::fs::write(&idx_path, [0u8; 3]).expect("writes fixture");
+
+        let err = read_offsets(&idx_path, span).expect_err("not a multiple of 8 bytes");
+        let ShellError::GenericError(_, msg, ..) = err else {
+            panic!("expected a GenericError")
+        };
+        assert!(msg.contains("not a valid index"));
+
+        std::fs::remove_file(&idx_path).ok();
+    }
+}
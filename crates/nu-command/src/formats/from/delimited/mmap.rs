@@ -0,0 +1,273 @@
+use std::fs::File;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use memmap2::Mmap;
+use nu_protocol::{DataSource, PipelineData, PipelineMetadata, ShellError, Span, Value};
+
+use super::{from_delimited_record_stream, reader_builder, CsvErrorMode, DelimitedReaderConfig};
+
+/// Returns the on-disk path backing `input`, if any. Only a real file known
+/// through `open`'s metadata is seekable and worth memory-mapping; piped or
+/// otherwise streamed input has no path and falls through to the regular
+/// buffered reader.
+pub(super) fn file_backed_source(input: &PipelineData) -> Option<(PathBuf, PipelineMetadata)> {
+    let metadata = input.metadata()?;
+    match &metadata.data_source {
+        DataSource::FilePath(path) => Some((path.clone(), metadata)),
+        _ => None,
+    }
+}
+
+fn csv_err(err: csv::Error, span: Span) -> ShellError {
+    ShellError::GenericError("CSVError".into(), err.to_string(), Some(span), None, vec![])
+}
+
+/// Attempts the memory-mapped fast path for `path`. Returns `None` (rather
+/// than an error) if the file can't be mapped, so the caller can fall back to
+/// `from_delimited_data`'s normal streaming reader.
+pub(super) fn read_mmapped(
+    config: &DelimitedReaderConfig,
+    path: &Path,
+    workers: usize,
+    span: Span,
+) -> Option<Result<Vec<Value>, ShellError>> {
+    let file = File::open(path).ok()?;
+    // Safety: we only read through the mapping for the duration of this
+    // call. If another process truncates or rewrites the file concurrently,
+    // reads may observe stale or torn data (the same risk any other
+    // open-then-mmap tool accepts) rather than the strong consistency a
+    // buffered read of an open file descriptor provides.
+    let mmap = unsafe { Mmap::map(&file) }.ok()?;
+
+    Some(read_mmap_bytes(config, &mmap, workers.max(1), span))
+}
+
+fn read_mmap_bytes(
+    config: &DelimitedReaderConfig,
+    bytes: &[u8],
+    workers: usize,
+    span: Span,
+) -> Result<Vec<Value>, ShellError> {
+    let mut reader = reader_builder(config, span)?.from_reader(Cursor::new(bytes));
+    let headers: Vec<String> = if config.noheaders {
+        (1..=reader
+            .headers()
+            .map_err(|err| csv_err(err, span))?
+            .len())
+            .map(|i| format!("column{i}"))
+            .collect()
+    } else {
+        reader
+            .headers()
+            .map_err(|err| csv_err(err, span))?
+            .iter()
+            .map(String::from)
+            .collect()
+    };
+    let header_offset = reader.position().byte() as usize;
+
+    // Parallel chunks run concurrently with no ordering guarantee between
+    // them, so a shared "stop at the first error" flag can't reproduce
+    // on_error: "abort"'s documented serial semantics - a later chunk can
+    // finish, and its rows get appended, before an earlier chunk's error is
+    // even discovered. Only the single-threaded path below preserves that
+    // guarantee, so force it for "abort" regardless of `workers`.
+    if workers <= 1 || config.on_error == CsvErrorMode::Abort {
+        return Ok(
+            from_delimited_record_stream(config.clone(), reader, headers, span).collect(),
+        );
+    }
+
+    // `csv::Reader::headers()` always physically consumes one record
+    // regardless of `has_headers` - for a headerless file that consumed
+    // record is real data, not a header line, so it must stay in the slice
+    // handed to the parallel chunker rather than being skipped like an
+    // actual header line is.
+    let data_start = if config.noheaders { 0 } else { header_offset };
+    let data = &bytes[data_start..];
+    let boundaries_span = Span::new((span.start + data_start).min(span.end), span.end);
+    let boundaries = record_start_offsets(config, data, boundaries_span)?;
+    let chunks = split_into_chunks(&boundaries, data.len(), workers);
+
+    let chunk_results: Vec<Vec<Value>> = thread::scope(|scope| {
+        chunks
+            .into_iter()
+            .map(|(start, end)| {
+                let headers = headers.clone();
+                let config = config.clone();
+                // `chunk_reader` below counts bytes from 0 at `data[start]`,
+                // so translate its error/row spans back to the file's real
+                // byte offsets or a parse error in chunk 2+ would be
+                // reported near the start of the file instead of where it
+                // actually occurred. Offsets past `span.end` are clamped to it.
+                let chunk_span =
+                    Span::new((span.start + data_start + start).min(span.end), span.end);
+                scope.spawn(move || -> Result<Vec<Value>, ShellError> {
+                    let chunk_reader = reader_builder(&config, chunk_span)?
+                        .has_headers(false)
+                        .from_reader(Cursor::new(&data[start..end]));
+                    Ok(
+                        from_delimited_record_stream(config, chunk_reader, headers, chunk_span)
+                            .collect::<Vec<Value>>(),
+                    )
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .map_err(|panic| {
+                        let reason = panic
+                            .downcast_ref::<&str>()
+                            .map(|s| s.to_string())
+                            .or_else(|| panic.downcast_ref::<String>().cloned())
+                            .unwrap_or_else(|| "unknown panic".into());
+                        ShellError::GenericError(
+                            "CSV parse error".into(),
+                            format!("a worker thread panicked while parsing a chunk: {reason}"),
+                            Some(span),
+                            None,
+                            vec![],
+                        )
+                    })
+                    .and_then(|result| result)
+            })
+            .collect::<Result<Vec<Vec<Value>>, ShellError>>()
+    })?;
+
+    // Each chunk ends with its own skip-and-collect summary error; move those
+    // after every chunk's rows so errors are still reported at the end.
+    let (errors, rows): (Vec<Value>, Vec<Value>) = chunk_results
+        .into_iter()
+        .flatten()
+        .partition(|value| matches!(value, Value::Error { .. }));
+    Ok(rows.into_iter().chain(errors).collect())
+}
+
+/// Byte offset, relative to `data`, of the start of each record in `data`.
+fn record_start_offsets(
+    config: &DelimitedReaderConfig,
+    data: &[u8],
+    span: Span,
+) -> Result<Vec<usize>, ShellError> {
+    let mut reader = reader_builder(config, span)?
+        .has_headers(false)
+        .from_reader(Cursor::new(data));
+
+    let mut offsets = vec![0usize];
+    let mut record = csv::ByteRecord::new();
+    loop {
+        if !reader
+            .read_byte_record(&mut record)
+            .map_err(|err| csv_err(err, span))?
+        {
+            break;
+        }
+        offsets.push(reader.position().byte() as usize);
+    }
+    // The last entry is the offset just past the final record (i.e. the end
+    // of `data`), which isn't the start of any record.
+    offsets.pop();
+    Ok(offsets)
+}
+
+/// Groups `record_starts` into at most `workers` contiguous `(start, end)`
+/// byte ranges, each aligned to a record boundary.
+fn split_into_chunks(
+    record_starts: &[usize],
+    data_len: usize,
+    workers: usize,
+) -> Vec<(usize, usize)> {
+    if record_starts.is_empty() {
+        return Vec::new();
+    }
+
+    let per_chunk = record_starts.len().div_ceil(workers.max(1)).max(1);
+    let chunk_starts: Vec<usize> = record_starts.iter().copied().step_by(per_chunk).collect();
+
+    chunk_starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = chunk_starts.get(i + 1).copied().unwrap_or(data_len);
+            (start, end)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_config() -> DelimitedReaderConfig {
+        DelimitedReaderConfig::default()
+    }
+
+    #[test]
+    fn split_into_chunks_partitions_on_record_boundaries() {
+        let starts = vec![0, 5, 10, 15, 20];
+        assert_eq!(split_into_chunks(&starts, 25, 2), vec![(0, 15), (15, 25)]);
+    }
+
+    #[test]
+    fn record_start_offsets_finds_each_record_boundary() {
+        let span = Span::test_data();
+        let config = base_config();
+        let data = b"1,2\n3,4\n5,6\n";
+        let offsets = record_start_offsets(&config, data, span).expect("scans records");
+        assert_eq!(offsets, vec![0, 4, 8]);
+    }
+
+    #[test]
+    fn parallel_read_matches_sequential_row_count() {
+        let span = Span::test_data();
+        let mut config = base_config();
+        // "abort" forces the serial path; "skip" lets the chunker run.
+        config.on_error = CsvErrorMode::SkipAndCollect;
+        let data = b"a,b\n1,2\n3,4\n5,6\n7,8\n".to_vec();
+
+        let sequential = read_mmap_bytes(&config, &data, 1, span).expect("sequential read");
+        let parallel = read_mmap_bytes(&config, &data, 3, span).expect("parallel read");
+
+        assert_eq!(sequential.len(), parallel.len());
+        assert_eq!(sequential.len(), 4);
+    }
+
+    #[test]
+    fn noheaders_parallel_read_keeps_the_first_row() {
+        // `csv::Reader::headers()` always physically consumes a record, even
+        // with `noheaders: true` - if that consumed record isn't put back
+        // into the slice handed to the parallel chunker, it's silently lost.
+        let span = Span::test_data();
+        let mut config = base_config();
+        config.noheaders = true;
+        config.on_error = CsvErrorMode::SkipAndCollect;
+        let data = b"1,2\n3,4\n5,6\n".to_vec();
+
+        let parallel = read_mmap_bytes(&config, &data, 2, span).expect("parallel read");
+
+        assert_eq!(parallel.len(), 3);
+    }
+
+    #[test]
+    fn abort_mode_forces_the_serial_path_even_with_multiple_workers() {
+        // Parallel chunks have no ordering guarantee between them, so
+        // on_error: "abort" can't be honored across workers - it must fall
+        // back to the single-threaded path, which halts at the first bad row
+        // regardless of how many workers were requested.
+        let span = Span::test_data();
+        let mut config = base_config();
+        config.on_error = CsvErrorMode::Abort;
+        let data = b"a,b\n1,2\n3\n4,5\n6,7\n".to_vec();
+
+        let serial = read_mmap_bytes(&config, &data, 1, span).expect("serial read");
+        let parallel = read_mmap_bytes(&config, &data, 4, span).expect("parallel read");
+
+        assert_eq!(parallel.len(), serial.len());
+        assert_eq!(parallel.len(), 2);
+        assert!(matches!(parallel[1], Value::Error { .. }));
+    }
+}
@@ -0,0 +1,238 @@
+use csv::{QuoteStyle, Terminator, WriterBuilder};
+use nu_protocol::{Record, ShellError, Span, Value};
+
+use crate::formats::from::delimited::char_to_u8;
+
+fn csv_write_err(err: csv::Error, span: Span) -> ShellError {
+    ShellError::GenericError("CSVError".into(), err.to_string(), Some(span), None, vec![])
+}
+
+pub fn quote_style_from_str(style: Option<Value>) -> Result<QuoteStyle, ShellError> {
+    match style {
+        Some(v) => {
+            let span = v.span();
+            match v {
+                Value::String { val: item, .. } => match item.as_str() {
+                    "always" => Ok(QuoteStyle::Always),
+                    "never" => Ok(QuoteStyle::Never),
+                    "non-numeric" => Ok(QuoteStyle::NonNumeric),
+                    "necessary" => Ok(QuoteStyle::Necessary),
+                    _ => Err(ShellError::TypeMismatch {
+                        err_message:
+                            "the only possible values for quote_style are 'always', 'never', 'non-numeric' and 'necessary'"
+                                .into(),
+                        span,
+                    }),
+                },
+                _ => Ok(QuoteStyle::Necessary),
+            }
+        }
+        None => Ok(QuoteStyle::Necessary),
+    }
+}
+
+/// Parses the value passed to `to csv --terminator`/`to tsv --terminator`: either
+/// the string `"CRLF"`, or a single character to use as the record terminator.
+pub fn terminator_from_value(terminator: Option<Value>, span: Span) -> Result<Terminator, ShellError> {
+    let Some(value) = terminator else {
+        return Ok(Terminator::Any(b'\n'));
+    };
+
+    let val_span = value.span();
+    let Value::String { val, .. } = value else {
+        return Err(ShellError::TypeMismatch {
+            err_message: "record terminator must be a string".into(),
+            span: val_span,
+        });
+    };
+
+    if val.eq_ignore_ascii_case("crlf") {
+        return Ok(Terminator::CRLF);
+    }
+
+    let mut chars = val.chars();
+    let first = chars.next().ok_or_else(|| ShellError::IncorrectValue {
+        msg: "record terminator cannot be empty".into(),
+        val_span,
+        call_span: span,
+    })?;
+    if chars.next().is_some() {
+        return Err(ShellError::IncorrectValue {
+            msg: "record terminator must be a single character or 'CRLF'".into(),
+            val_span,
+            call_span: span,
+        });
+    }
+
+    Ok(Terminator::Any(char_to_u8(first, "terminator", val_span)?))
+}
+
+/// Mirrors [`crate::formats::from::delimited::DelimitedReaderConfig`] for the write side, so
+/// `from csv ... | to csv ...` can round-trip losslessly.
+pub(super) struct DelimitedWriterConfig {
+    pub separator: char,
+    pub quote: char,
+    pub escape: Option<char>,
+    pub quote_style: QuoteStyle,
+    pub terminator: Terminator,
+    pub noheaders: bool,
+}
+
+fn value_to_field(value: &Value) -> Result<String, ShellError> {
+    match value {
+        Value::String { val, .. } => Ok(val.clone()),
+        Value::Int { val, .. } => Ok(val.to_string()),
+        Value::Float { val, .. } => Ok(val.to_string()),
+        Value::Bool { val, .. } => Ok(val.to_string()),
+        Value::Date { val, .. } => Ok(val.to_rfc3339()),
+        Value::Binary { val, .. } => String::from_utf8(val.clone()).map_err(|_| {
+            ShellError::UnsupportedInput {
+                msg: "CSV/TSV cannot represent non-UTF-8 binary data losslessly".into(),
+                input: value.get_type().to_string(),
+                msg_span: value.span(),
+                input_span: value.span(),
+            }
+        }),
+        Value::Nothing { .. } => Ok(String::new()),
+        other => Err(ShellError::UnsupportedInput {
+            msg: format!("CSV/TSV cannot represent a nested {}", other.get_type()),
+            input: other.get_type().to_string(),
+            msg_span: other.span(),
+            input_span: other.span(),
+        }),
+    }
+}
+
+fn rows_of(input: Value, span: Span) -> Result<Vec<Record>, ShellError> {
+    match input {
+        Value::List { vals, .. } => vals
+            .into_iter()
+            .map(|row| match row {
+                Value::Record { val, .. } => Ok(val),
+                other => Err(ShellError::UnsupportedInput {
+                    msg: "Expected a table of records for CSV/TSV output".into(),
+                    input: other.get_type().to_string(),
+                    msg_span: span,
+                    input_span: other.span(),
+                }),
+            })
+            .collect(),
+        Value::Record { val, .. } => Ok(vec![val]),
+        other => Err(ShellError::UnsupportedInput {
+            msg: "Expected a table or record to convert to CSV/TSV".into(),
+            input: other.get_type().to_string(),
+            msg_span: span,
+            input_span: other.span(),
+        }),
+    }
+}
+
+pub(super) fn to_delimited_data(
+    config: DelimitedWriterConfig,
+    input: Value,
+    span: Span,
+) -> Result<Value, ShellError> {
+    let rows = rows_of(input, span)?;
+
+    let mut headers: Vec<String> = Vec::new();
+    for row in &rows {
+        for col in row.columns() {
+            if !headers.iter().any(|h| h == col) {
+                headers.push(col.clone());
+            }
+        }
+    }
+
+    let mut builder = WriterBuilder::new();
+    builder
+        .delimiter(char_to_u8(config.separator, "separator", span)?)
+        .quote(char_to_u8(config.quote, "quote", span)?)
+        .quote_style(config.quote_style)
+        .terminator(config.terminator);
+    if let Some(escape) = config.escape {
+        builder
+            .double_quote(false)
+            .escape(char_to_u8(escape, "escape", span)?);
+    }
+    let mut writer = builder.from_writer(vec![]);
+
+    if !config.noheaders {
+        writer
+            .write_record(headers.iter().map(String::as_str))
+            .map_err(|err| csv_write_err(err, span))?;
+    }
+
+    for row in &rows {
+        let mut fields = Vec::with_capacity(headers.len());
+        for header in &headers {
+            let field = row
+                .get(header)
+                .map(value_to_field)
+                .transpose()?
+                .unwrap_or_default();
+            fields.push(field);
+        }
+        writer
+            .write_record(&fields)
+            .map_err(|err| csv_write_err(err, span))?;
+    }
+
+    let bytes = writer.into_inner().map_err(|err| {
+        ShellError::GenericError(
+            "CSVError".into(),
+            err.into_error().to_string(),
+            Some(span),
+            None,
+            vec![],
+        )
+    })?;
+    let text = String::from_utf8(bytes).map_err(|err| {
+        ShellError::GenericError(
+            "CSV encoding error".into(),
+            err.to_string(),
+            Some(span),
+            None,
+            vec![],
+        )
+    })?;
+
+    Ok(Value::string(text, span))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_to_field_rejects_non_utf8_binary() {
+        let span = Span::test_data();
+        let value = Value::binary(vec![0xff, 0xfe], span);
+        value_to_field(&value).expect_err(
+            "non-UTF-8 binary can't round-trip through from_utf8_lossy without corruption",
+        );
+    }
+
+    #[test]
+    fn to_delimited_data_writes_header_and_rows() {
+        let span = Span::test_data();
+        let row = Record {
+            cols: vec!["a".to_string(), "b".to_string()],
+            vals: vec![Value::int(1, span), Value::string("x".to_string(), span)],
+        };
+        let input = Value::list(vec![Value::record(row, span)], span);
+        let config = DelimitedWriterConfig {
+            separator: ',',
+            quote: '"',
+            escape: None,
+            quote_style: QuoteStyle::Necessary,
+            terminator: Terminator::Any(b'\n'),
+            noheaders: false,
+        };
+
+        let output = to_delimited_data(config, input, span).expect("writes csv");
+        let Value::String { val, .. } = output else {
+            panic!("expected a string")
+        };
+        assert_eq!(val, "a,b\n1,x\n");
+    }
+}